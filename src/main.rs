@@ -1,11 +1,17 @@
 use anyhow::{anyhow, Result};
+use base64::Engine;
+use futures_util::{SinkExt, StreamExt};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::env;
 use std::fs;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader as TokioBufReader};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader as TokioBufReader};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::connect_async;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct McpRequest {
@@ -32,6 +38,44 @@ struct McpError {
     data: Option<Value>,
 }
 
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+const INTERNAL_ERROR: i32 = -32603;
+
+/// An error carrying a specific JSON-RPC error code, as opposed to the
+/// generic internal error that other `anyhow::Error`s map to.
+#[derive(Debug)]
+struct RpcError {
+    code: i32,
+    message: String,
+}
+
+impl std::fmt::Display for RpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+fn method_not_found(message: impl Into<String>) -> anyhow::Error {
+    RpcError {
+        code: METHOD_NOT_FOUND,
+        message: message.into(),
+    }
+    .into()
+}
+
+fn invalid_params(message: impl Into<String>) -> anyhow::Error {
+    RpcError {
+        code: INVALID_PARAMS,
+        message: message.into(),
+    }
+    .into()
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct ToolCall {
     name: String,
@@ -50,6 +94,43 @@ struct DeepgramTtsResponse {
     data: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct DeepgramSttRequest {
+    url: String,
+}
+
+fn extension_for_encoding(encoding: &str) -> &'static str {
+    match encoding {
+        "linear16" => "wav",
+        "flac" => "flac",
+        "opus" => "opus",
+        "mp3" => "mp3",
+        _ => "mp3",
+    }
+}
+
+fn mime_for_encoding(encoding: &str) -> &'static str {
+    match encoding {
+        "linear16" => "audio/wav",
+        "flac" => "audio/flac",
+        "opus" => "audio/opus",
+        "mp3" => "audio/mpeg",
+        _ => "audio/mpeg",
+    }
+}
+
+fn content_type_for_path(path: &str) -> &'static str {
+    match path.rsplit('.').next().unwrap_or("").to_lowercase().as_str() {
+        "wav" => "audio/wav",
+        "mp3" => "audio/mpeg",
+        "flac" => "audio/flac",
+        "ogg" => "audio/ogg",
+        "m4a" => "audio/mp4",
+        "webm" => "audio/webm",
+        _ => "audio/wav",
+    }
+}
+
 struct DeepgramMcpServer {
     client: Client,
     api_key: String,
@@ -66,9 +147,33 @@ impl DeepgramMcpServer {
         })
     }
 
-    async fn generate_audio(&self, text: &str) -> Result<Vec<u8>> {
-        let url = "https://api.deepgram.com/v1/speak?model=aura-asteria-en";
-        
+    async fn generate_audio(
+        &self,
+        text: &str,
+        model: &str,
+        encoding: &str,
+        sample_rate: Option<u32>,
+        bit_rate: Option<u32>,
+    ) -> Result<Vec<u8>> {
+        let mut url = format!(
+            "https://api.deepgram.com/v1/speak?model={}&encoding={}",
+            model, encoding
+        );
+
+        if let Some(sample_rate) = sample_rate {
+            url.push_str(&format!("&sample_rate={}", sample_rate));
+        }
+
+        if let Some(bit_rate) = bit_rate {
+            url.push_str(&format!("&bit_rate={}", bit_rate));
+        }
+
+        if encoding == "linear16" {
+            // linear16 is headerless raw PCM unless wrapped in a container;
+            // request WAV so the bytes match the .wav extension/mimeType we hand back.
+            url.push_str("&container=wav");
+        }
+
         let request_body = json!({
             "text": text
         });
@@ -91,6 +196,236 @@ impl DeepgramMcpServer {
         Ok(audio_data.to_vec())
     }
 
+    async fn transcribe_file(
+        &self,
+        path: &str,
+        model: &str,
+        language: Option<&str>,
+        smart_format: bool,
+        diarize: bool,
+        punctuate: bool,
+    ) -> Result<Value> {
+        let audio_data = fs::read(path)?;
+        let content_type = content_type_for_path(path);
+
+        let url = self.build_listen_url(model, language, smart_format, diarize, punctuate);
+
+        let response = self
+            .client
+            .post(url)
+            .header("Authorization", format!("Token {}", self.api_key))
+            .header("Content-Type", content_type)
+            .body(audio_data)
+            .send()
+            .await?;
+
+        self.parse_listen_response(response).await
+    }
+
+    async fn transcribe_url(
+        &self,
+        audio_url: &str,
+        model: &str,
+        language: Option<&str>,
+        smart_format: bool,
+        diarize: bool,
+        punctuate: bool,
+    ) -> Result<Value> {
+        let url = self.build_listen_url(model, language, smart_format, diarize, punctuate);
+
+        let request_body = DeepgramSttRequest {
+            url: audio_url.to_string(),
+        };
+
+        let response = self
+            .client
+            .post(url)
+            .header("Authorization", format!("Token {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await?;
+
+        self.parse_listen_response(response).await
+    }
+
+    fn build_listen_url(
+        &self,
+        model: &str,
+        language: Option<&str>,
+        smart_format: bool,
+        diarize: bool,
+        punctuate: bool,
+    ) -> String {
+        let mut url = format!(
+            "https://api.deepgram.com/v1/listen?model={}&smart_format={}&diarize={}&punctuate={}",
+            model, smart_format, diarize, punctuate
+        );
+
+        if let Some(language) = language {
+            url.push_str(&format!("&language={}", language));
+        }
+
+        url
+    }
+
+    async fn parse_listen_response(&self, response: reqwest::Response) -> Result<Value> {
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("Deepgram API error: {}", error_text));
+        }
+
+        let body: Value = response.json().await?;
+
+        let channel = &body["results"]["channels"][0]["alternatives"][0];
+        let transcript = channel["transcript"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Deepgram response missing transcript"))?
+            .to_string();
+
+        Ok(json!({
+            "transcript": transcript,
+            "words": channel.get("words").cloned().unwrap_or(Value::Null),
+        }))
+    }
+
+    async fn live_transcribe(&self, file_path: Option<String>, model: &str) -> Result<String> {
+        const FRAME_BYTES: usize = 3200; // ~100ms of 16kHz 16-bit mono PCM
+        const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(3);
+
+        let url = format!(
+            "wss://api.deepgram.com/v1/listen?model={}&encoding=linear16&sample_rate=16000",
+            model
+        );
+
+        let mut request = url.into_client_request()?;
+        request
+            .headers_mut()
+            .insert("Authorization", format!("Token {}", self.api_key).parse()?);
+
+        let (ws_stream, _) = connect_async(request).await?;
+        let (mut write, mut read) = ws_stream.split();
+
+        let mut source: Box<dyn tokio::io::AsyncRead + Unpin + Send> = match file_path {
+            Some(path) => Box::new(tokio::fs::File::open(&path).await?),
+            None => Box::new(tokio::io::stdin()),
+        };
+
+        let writer_task = tokio::spawn(async move {
+            let mut buf = vec![0u8; FRAME_BYTES];
+            loop {
+                match tokio::time::timeout(KEEPALIVE_INTERVAL, source.read(&mut buf)).await {
+                    Ok(Ok(0)) => break,
+                    Ok(Ok(n)) => {
+                        if write.send(Message::Binary(buf[..n].to_vec())).await.is_err() {
+                            break;
+                        }
+                    }
+                    Ok(Err(_)) => break,
+                    Err(_) => {
+                        if write
+                            .send(Message::Text(json!({"type": "KeepAlive"}).to_string()))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            let _ = write
+                .send(Message::Text(json!({"type": "CloseStream"}).to_string()))
+                .await;
+
+            Ok::<(), anyhow::Error>(())
+        });
+
+        let mut finals = Vec::new();
+        while let Some(message) = read.next().await {
+            let message = message?;
+            match message {
+                Message::Text(text) => {
+                    let value: Value = serde_json::from_str(&text)?;
+
+                    if value.get("from_finalize").and_then(|v| v.as_bool()) == Some(true) {
+                        break;
+                    }
+
+                    if value.get("is_final").and_then(|v| v.as_bool()) == Some(true) {
+                        if let Some(transcript) =
+                            value["channel"]["alternatives"][0]["transcript"].as_str()
+                        {
+                            if !transcript.is_empty() {
+                                finals.push(transcript.to_string());
+                            }
+                        }
+                    }
+                }
+                Message::Close(_) => break,
+                _ => {}
+            }
+        }
+
+        writer_task.await??;
+
+        Ok(finals.join(" "))
+    }
+
+    async fn get_json(&self, url: &str) -> Result<Value> {
+        let response = self
+            .client
+            .get(url)
+            .header("Authorization", format!("Token {}", self.api_key))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("Deepgram API error: {}", error_text));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    async fn list_projects(&self) -> Result<Value> {
+        self.get_json("https://api.deepgram.com/v1/projects").await
+    }
+
+    async fn get_usage(
+        &self,
+        project_id: &str,
+        start: Option<&str>,
+        end: Option<&str>,
+    ) -> Result<Value> {
+        let mut url = format!(
+            "https://api.deepgram.com/v1/projects/{}/usage",
+            project_id
+        );
+
+        let mut query = Vec::new();
+        if let Some(start) = start {
+            query.push(format!("start={}", start));
+        }
+        if let Some(end) = end {
+            query.push(format!("end={}", end));
+        }
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&query.join("&"));
+        }
+
+        self.get_json(&url).await
+    }
+
+    async fn list_balances(&self, project_id: &str) -> Result<Value> {
+        self.get_json(&format!(
+            "https://api.deepgram.com/v1/projects/{}/balances",
+            project_id
+        ))
+        .await
+    }
+
     async fn handle_list_tools(&self) -> Result<Value> {
         Ok(json!({
             "tools": [
@@ -107,10 +442,128 @@ impl DeepgramMcpServer {
                             "filename": {
                                 "type": "string",
                                 "description": "The filename for the output audio file (optional, defaults to 'output.mp3')"
+                            },
+                            "return_inline": {
+                                "type": "boolean",
+                                "description": "Return the generated audio as base64-encoded inline content instead of writing it to a file (optional, defaults to false)"
+                            },
+                            "model": {
+                                "type": "string",
+                                "description": "The Deepgram Aura voice to use (optional, defaults to 'aura-asteria-en')"
+                            },
+                            "encoding": {
+                                "type": "string",
+                                "description": "The output audio encoding: 'mp3', 'linear16', 'flac', or 'opus' (optional, defaults to 'mp3')"
+                            },
+                            "sample_rate": {
+                                "type": "integer",
+                                "description": "The output sample rate in Hz (optional, required for some encodings like linear16)"
+                            },
+                            "bit_rate": {
+                                "type": "integer",
+                                "description": "The output bit rate in bits per second (optional, only applies to compressed encodings)"
                             }
                         },
                         "required": ["text"]
                     }
+                },
+                {
+                    "name": "deepgram_speech_to_text",
+                    "description": "Transcribe audio to text using Deepgram's speech-to-text API. Accepts either a local file path or a remote URL.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "file_path": {
+                                "type": "string",
+                                "description": "Path to a local audio file to transcribe"
+                            },
+                            "url": {
+                                "type": "string",
+                                "description": "URL of a remote audio file to transcribe"
+                            },
+                            "model": {
+                                "type": "string",
+                                "description": "The Deepgram model to use for transcription (optional, defaults to 'nova-2')"
+                            },
+                            "language": {
+                                "type": "string",
+                                "description": "The language of the audio (optional, Deepgram auto-detects if omitted)"
+                            },
+                            "smart_format": {
+                                "type": "boolean",
+                                "description": "Apply smart formatting (punctuation, paragraphs, etc.) to the transcript (optional, defaults to true)"
+                            },
+                            "diarize": {
+                                "type": "boolean",
+                                "description": "Include speaker diarization and word-level timestamps (optional, defaults to false)"
+                            },
+                            "punctuate": {
+                                "type": "boolean",
+                                "description": "Add punctuation to the transcript (optional, defaults to true)"
+                            }
+                        }
+                    }
+                },
+                {
+                    "name": "deepgram_live_transcribe",
+                    "description": "Stream raw 16-bit 16kHz mono PCM audio to Deepgram's real-time transcription API and return the aggregated final transcript.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "file_path": {
+                                "type": "string",
+                                "description": "Path to a raw PCM audio file to stream (optional; reads from stdin if omitted)"
+                            },
+                            "model": {
+                                "type": "string",
+                                "description": "The Deepgram model to use for transcription (optional, defaults to 'nova-2')"
+                            }
+                        }
+                    }
+                },
+                {
+                    "name": "deepgram_list_projects",
+                    "description": "List the Deepgram projects accessible to this API key.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {}
+                    }
+                },
+                {
+                    "name": "deepgram_get_usage",
+                    "description": "Get usage requests for a Deepgram project, optionally filtered by a date range.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "project_id": {
+                                "type": "string",
+                                "description": "The ID of the project to get usage for"
+                            },
+                            "start": {
+                                "type": "string",
+                                "description": "Start date for the usage range, e.g. '2024-01-01' (optional)"
+                            },
+                            "end": {
+                                "type": "string",
+                                "description": "End date for the usage range, e.g. '2024-01-31' (optional)"
+                            }
+                        },
+                        "required": ["project_id"]
+                    }
+                },
+                {
+                    "name": "deepgram_list_balances",
+                    "description": "List the prepay credit balances for a Deepgram project.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "project_id": {
+                                "type": "string",
+                                "description": "The ID of the project to get balances for"
+                            }
+                        },
+                        "required": ["project_id"]
+                    }
                 }
             ]
         }))
@@ -122,31 +575,203 @@ impl DeepgramMcpServer {
                 let text = arguments
                     .get("text")
                     .and_then(|v| v.as_str())
-                    .ok_or_else(|| anyhow!("Missing 'text' parameter"))?;
+                    .ok_or_else(|| invalid_params("Missing 'text' parameter"))?;
+
+                let return_inline = arguments
+                    .get("return_inline")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+
+                let model = arguments
+                    .get("model")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("aura-asteria-en");
+
+                let encoding = arguments
+                    .get("encoding")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("mp3");
+
+                let sample_rate = arguments
+                    .get("sample_rate")
+                    .and_then(|v| v.as_u64())
+                    .map(|v| v as u32);
+
+                let bit_rate = arguments
+                    .get("bit_rate")
+                    .and_then(|v| v.as_u64())
+                    .map(|v| v as u32);
+
+                let audio_data = self
+                    .generate_audio(text, model, encoding, sample_rate, bit_rate)
+                    .await?;
+
+                if return_inline {
+                    let tts_response = DeepgramTtsResponse {
+                        content_type: mime_for_encoding(encoding).to_string(),
+                        data: base64::engine::general_purpose::STANDARD.encode(&audio_data),
+                    };
+
+                    Ok(json!({
+                        "content": [
+                            {
+                                "type": "audio",
+                                "data": tts_response.data,
+                                "mimeType": tts_response.content_type
+                            }
+                        ]
+                    }))
+                } else {
+                    let filename = arguments
+                        .get("filename")
+                        .map(|v| v.as_str().unwrap_or("output").to_string())
+                        .unwrap_or_else(|| format!("output.{}", extension_for_encoding(encoding)));
+
+                    fs::write(&filename, &audio_data)?;
+
+                    Ok(json!({
+                        "content": [
+                            {
+                                "type": "text",
+                                "text": format!("Successfully generated audio file '{}' from text: \"{}\"", filename, text)
+                            }
+                        ]
+                    }))
+                }
+            }
+            "deepgram_speech_to_text" => {
+                let model = arguments
+                    .get("model")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("nova-2");
+
+                let language = arguments.get("language").and_then(|v| v.as_str());
+
+                let smart_format = arguments
+                    .get("smart_format")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+
+                let diarize = arguments
+                    .get("diarize")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+
+                let punctuate = arguments
+                    .get("punctuate")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+
+                let result = if let Some(url) = arguments.get("url").and_then(|v| v.as_str()) {
+                    self.transcribe_url(url, model, language, smart_format, diarize, punctuate)
+                        .await?
+                } else if let Some(file_path) =
+                    arguments.get("file_path").and_then(|v| v.as_str())
+                {
+                    self.transcribe_file(file_path, model, language, smart_format, diarize, punctuate)
+                        .await?
+                } else {
+                    return Err(invalid_params("Either 'file_path' or 'url' parameter is required"));
+                };
+
+                let transcript = result["transcript"].as_str().unwrap_or("").to_string();
+
+                let mut text = format!("Transcript: {}", transcript);
+                if diarize {
+                    if let Some(words) = result.get("words").filter(|w| !w.is_null()) {
+                        text.push_str(&format!("\n\nWords:\n{}", serde_json::to_string_pretty(words)?));
+                    }
+                }
+
+                Ok(json!({
+                    "content": [
+                        {
+                            "type": "text",
+                            "text": text
+                        }
+                    ]
+                }))
+            }
+            "deepgram_live_transcribe" => {
+                let file_path = arguments
+                    .get("file_path")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string());
+
+                let model = arguments
+                    .get("model")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("nova-2");
+
+                let transcript = self.live_transcribe(file_path, model).await?;
+
+                Ok(json!({
+                    "content": [
+                        {
+                            "type": "text",
+                            "text": format!("Transcript: {}", transcript)
+                        }
+                    ]
+                }))
+            }
+            "deepgram_list_projects" => {
+                let projects = self.list_projects().await?;
+
+                Ok(json!({
+                    "content": [
+                        {
+                            "type": "text",
+                            "text": serde_json::to_string_pretty(&projects)?
+                        }
+                    ]
+                }))
+            }
+            "deepgram_get_usage" => {
+                let project_id = arguments
+                    .get("project_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| invalid_params("Missing 'project_id' parameter"))?;
 
-                let filename = arguments
-                    .get("filename")
+                let start = arguments.get("start").and_then(|v| v.as_str());
+                let end = arguments.get("end").and_then(|v| v.as_str());
+
+                let usage = self.get_usage(project_id, start, end).await?;
+
+                Ok(json!({
+                    "content": [
+                        {
+                            "type": "text",
+                            "text": serde_json::to_string_pretty(&usage)?
+                        }
+                    ]
+                }))
+            }
+            "deepgram_list_balances" => {
+                let project_id = arguments
+                    .get("project_id")
                     .and_then(|v| v.as_str())
-                    .unwrap_or("output.mp3");
+                    .ok_or_else(|| invalid_params("Missing 'project_id' parameter"))?;
 
-                let audio_data = self.generate_audio(text).await?;
-                
-                fs::write(filename, &audio_data)?;
+                let balances = self.list_balances(project_id).await?;
 
                 Ok(json!({
                     "content": [
                         {
                             "type": "text",
-                            "text": format!("Successfully generated audio file '{}' from text: \"{}\"", filename, text)
+                            "text": serde_json::to_string_pretty(&balances)?
                         }
                     ]
                 }))
             }
-            _ => Err(anyhow!("Unknown tool: {}", name)),
+            _ => Err(method_not_found(format!("Unknown tool: {}", name))),
         }
     }
 
-    async fn handle_request(&self, request: McpRequest) -> McpResponse {
+    /// Handles a single JSON-RPC request. Returns `None` for notifications
+    /// (requests with no `id`), since the spec forbids responding to those.
+    async fn handle_request(&self, request: McpRequest) -> Option<McpResponse> {
+        let is_notification = request.id.is_none();
+
         let result = match request.method.as_str() {
             "initialize" => {
                 Ok(json!({
@@ -178,32 +803,80 @@ impl DeepgramMcpServer {
 
                                 self.handle_call_tool(name, &arguments).await
                             }
-                            None => Err(anyhow!("Missing tool name"))
+                            None => Err(invalid_params("Missing tool name"))
                         }
                     }
-                    None => Err(anyhow!("Missing params"))
+                    None => Err(invalid_params("Missing params"))
                 }
             }
-            _ => Err(anyhow!("Unknown method: {}", request.method)),
+            _ => Err(method_not_found(format!("Unknown method: {}", request.method))),
         };
 
-        match result {
+        if is_notification {
+            return None;
+        }
+
+        Some(match result {
             Ok(result) => McpResponse {
                 jsonrpc: "2.0".to_string(),
                 id: request.id,
                 result: Some(result),
                 error: None,
             },
-            Err(e) => McpResponse {
-                jsonrpc: "2.0".to_string(),
-                id: request.id,
-                result: None,
-                error: Some(McpError {
-                    code: -32603,
-                    message: e.to_string(),
-                    data: None,
-                }),
-            },
+            Err(e) => {
+                let code = e
+                    .downcast_ref::<RpcError>()
+                    .map(|rpc_err| rpc_err.code)
+                    .unwrap_or(INTERNAL_ERROR);
+
+                McpResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: request.id,
+                    result: None,
+                    error: Some(McpError {
+                        code,
+                        message: e.to_string(),
+                        data: None,
+                    }),
+                }
+            }
+        })
+    }
+
+    fn parse_error_response(err: &serde_json::Error) -> McpResponse {
+        McpResponse {
+            jsonrpc: "2.0".to_string(),
+            id: None,
+            result: None,
+            error: Some(McpError {
+                code: PARSE_ERROR,
+                message: format!("Parse error: {}", err),
+                data: None,
+            }),
+        }
+    }
+
+    fn invalid_request_response(id: Option<Value>, err: &serde_json::Error) -> McpResponse {
+        McpResponse {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result: None,
+            error: Some(McpError {
+                code: INVALID_REQUEST,
+                message: format!("Invalid Request: {}", err),
+                data: None,
+            }),
+        }
+    }
+
+    /// Runs a single parsed JSON value (a request object, or one element of
+    /// a batch) through `handle_request`, returning its response if any.
+    async fn dispatch_value(&self, value: Value) -> Option<McpResponse> {
+        let id = value.get("id").cloned();
+
+        match serde_json::from_value::<McpRequest>(value) {
+            Ok(request) => self.handle_request(request).await,
+            Err(e) => Some(Self::invalid_request_response(id, &e)),
         }
     }
 
@@ -223,17 +896,38 @@ impl DeepgramMcpServer {
                         continue;
                     }
 
-                    match serde_json::from_str::<McpRequest>(trimmed) {
-                        Ok(request) => {
-                            let response = self.handle_request(request).await;
+                    match serde_json::from_str::<Value>(trimmed) {
+                        Ok(Value::Array(items)) => {
+                            let mut responses = Vec::with_capacity(items.len());
+                            for item in items {
+                                if let Some(response) = self.dispatch_value(item).await {
+                                    responses.push(response);
+                                }
+                            }
+
+                            if !responses.is_empty() {
+                                let response_json = serde_json::to_string(&responses)?;
+                                stdout.write_all(response_json.as_bytes()).await?;
+                                stdout.write_all(b"\n").await?;
+                                stdout.flush().await?;
+                            }
+                        }
+                        Ok(value) => {
+                            if let Some(response) = self.dispatch_value(value).await {
+                                let response_json = serde_json::to_string(&response)?;
+                                stdout.write_all(response_json.as_bytes()).await?;
+                                stdout.write_all(b"\n").await?;
+                                stdout.flush().await?;
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to parse request: {}", e);
+                            let response = Self::parse_error_response(&e);
                             let response_json = serde_json::to_string(&response)?;
                             stdout.write_all(response_json.as_bytes()).await?;
                             stdout.write_all(b"\n").await?;
                             stdout.flush().await?;
                         }
-                        Err(e) => {
-                            eprintln!("Failed to parse request: {}", e);
-                        }
                     }
                 }
                 Err(e) => {